@@ -2,7 +2,8 @@ use fuzzy_matcher::skim::SkimMatcherV2;
 use fuzzy_matcher::FuzzyMatcher;
 use pyo3::exceptions::{PyRuntimeError, PyTypeError, PyValueError};
 use pyo3::prelude::*;
-use pyo3::types::{PyList, PySequence, PyString};
+use pyo3::types::{PyList, PySequence, PyString, PyTuple};
+use rayon::prelude::*;
 use skim::prelude::*;
 use std::borrow::Cow;
 use std::sync::Arc;
@@ -23,14 +24,15 @@ fn perform_fuzzy_match<'a>(
     query: &str,
     items: &'a [String],
     interactive: bool,
+    case: CaseMode,
 ) -> PyResult<Vec<&'a String>> {
     if items.is_empty() {
         return Ok(Vec::new());
     }
 
     match interactive {
-        true => perform_interactive_match(query, items),
-        false => perform_non_interactive_match(query, items),
+        true => perform_interactive_match(query, items, case),
+        false => perform_non_interactive_match(query, items, case),
     }
 }
 
@@ -57,12 +59,17 @@ impl SkimItem for StringItem {
 ///
 /// Returns:
 ///     A vector of matched strings or PyErr if something fails
-fn perform_interactive_match<'a>(query: &str, items: &'a [String]) -> PyResult<Vec<&'a String>> {
+fn perform_interactive_match<'a>(
+    query: &str,
+    items: &'a [String],
+    case: CaseMode,
+) -> PyResult<Vec<&'a String>> {
     let options = SkimOptionsBuilder::default()
         .height("100%".to_string())
         .query(Some(query.to_string()))
         .multi(true)
         .interactive(true)
+        .case(case.skim_case())
         .build()
         .map_err(|err| PyRuntimeError::new_err(format!("Failed to build skim options: {}", err)))?;
 
@@ -105,6 +112,321 @@ fn perform_interactive_match<'a>(query: &str, items: &'a [String]) -> PyResult<V
         .collect())
 }
 
+/// Split a query into independent sub-queries on unescaped spaces.
+///
+/// Every resulting term must match an item for it to be considered a hit,
+/// mirroring the space-separated AND behaviour of fzf/skim. A space escaped
+/// as `\ ` is kept as a literal space inside the surrounding term. Empty
+/// terms (e.g. from runs of spaces) are dropped.
+fn split_query(query: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    let mut current = String::new();
+    let mut saw_backslash = false;
+
+    for ch in query.chars() {
+        if ch == ' ' && !saw_backslash {
+            if !current.is_empty() {
+                terms.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(ch);
+        }
+        saw_backslash = ch == '\\';
+    }
+    if !current.is_empty() {
+        terms.push(current);
+    }
+
+    terms
+        .into_iter()
+        .map(|term| term.replace("\\ ", " "))
+        .filter(|term| !term.is_empty())
+        .collect()
+}
+
+/// How the fuzzy matcher treats letter case.
+#[derive(Clone, Copy)]
+enum CaseMode {
+    /// Case-insensitive unless the query contains an uppercase letter (default).
+    Smart,
+    /// Always case-insensitive.
+    Ignore,
+    /// Always case-sensitive.
+    Respect,
+}
+
+impl CaseMode {
+    /// Parse the `case` argument, defaulting to smart-case when absent.
+    fn parse(value: Option<&str>) -> PyResult<CaseMode> {
+        match value {
+            None | Some("smart") => Ok(CaseMode::Smart),
+            Some("ignore") => Ok(CaseMode::Ignore),
+            Some("respect") => Ok(CaseMode::Respect),
+            Some(other) => Err(PyValueError::new_err(format!(
+                "invalid case mode: '{}' (expected 'smart', 'ignore', or 'respect')",
+                other
+            ))),
+        }
+    }
+
+    /// Build a `SkimMatcherV2` for this mode. Smart-case resolves to
+    /// case-sensitive when `query` contains any uppercase character.
+    fn matcher(self, query: &str) -> SkimMatcherV2 {
+        let base = SkimMatcherV2::default();
+        match self {
+            CaseMode::Ignore => base.ignore_case(),
+            CaseMode::Respect => base.respect_case(),
+            CaseMode::Smart => {
+                if query.chars().any(|c| c.is_uppercase()) {
+                    base.respect_case()
+                } else {
+                    base.ignore_case()
+                }
+            }
+        }
+    }
+
+    /// The equivalent skim `CaseMatching` for the interactive path.
+    fn skim_case(self) -> CaseMatching {
+        match self {
+            CaseMode::Smart => CaseMatching::Smart,
+            CaseMode::Ignore => CaseMatching::Ignore,
+            CaseMode::Respect => CaseMatching::Respect,
+        }
+    }
+}
+
+/// The matching strategy a query atom applies to an item.
+enum AtomKind {
+    /// Fuzzy match via `SkimMatcherV2` (the default).
+    Fuzzy,
+    /// Plain substring match (`'foo`).
+    Substring,
+    /// Prefix-anchored match (`^foo`).
+    Prefix,
+    /// Suffix-anchored match (`foo$`).
+    Postfix,
+    /// Exact equality (`^foo$`).
+    Exact,
+}
+
+/// A single parsed query term with its matching strategy and flags.
+///
+/// Atoms are produced from the space-separated terms of a query and combined
+/// with AND semantics, exposing the fzf-style operators `^`, `'`, `$` and `!`.
+struct QueryAtom {
+    kind: AtomKind,
+    text: String,
+    /// When set the atom must *not* match (`!foo`) and contributes no score.
+    inverse: bool,
+    /// Smart-case flag for the non-fuzzy kinds: an all-lowercase atom compares
+    /// case-insensitively, otherwise the comparison is case-sensitive.
+    ignore_case: bool,
+}
+
+impl QueryAtom {
+    /// Parse a single (already space-split and unescaped) term into an atom.
+    ///
+    /// Returns `None` when stripping the sigils leaves an empty atom, in which
+    /// case the term is ignored.
+    fn parse(term: &str) -> Option<QueryAtom> {
+        let mut rest = term;
+
+        let inverse = rest.starts_with('!');
+        if inverse {
+            rest = &rest[1..];
+        }
+
+        let prefix = rest.starts_with('^');
+        let postfix = rest.ends_with('$') && !rest.ends_with("\\$");
+
+        let (kind, body): (AtomKind, &str) = if prefix && postfix && rest.len() >= 2 {
+            (AtomKind::Exact, &rest[1..rest.len() - 1])
+        } else if prefix {
+            (AtomKind::Prefix, &rest[1..])
+        } else if postfix {
+            (AtomKind::Postfix, &rest[..rest.len() - 1])
+        } else if let Some(stripped) = rest.strip_prefix('\'') {
+            (AtomKind::Substring, stripped)
+        } else {
+            (AtomKind::Fuzzy, rest)
+        };
+
+        let text = body.replace("\\$", "$");
+        if text.is_empty() {
+            return None;
+        }
+
+        let ignore_case = !text.chars().any(|c| c.is_ascii_uppercase());
+
+        Some(QueryAtom {
+            kind,
+            text,
+            inverse,
+            ignore_case,
+        })
+    }
+
+    /// Evaluate the atom against `item`, returning whether it matched and the
+    /// fuzzy score and matched character positions it contributes (only fuzzy,
+    /// non-inverse atoms score). Character positions are only computed when
+    /// `collect_indices` is set, which uses the slightly pricier
+    /// `fuzzy_indices` call.
+    fn evaluate(
+        &self,
+        matcher: &SkimMatcherV2,
+        item: &str,
+        collect_indices: bool,
+    ) -> (bool, Option<i64>, Vec<usize>) {
+        let (matched, score, indices) = match self.kind {
+            AtomKind::Fuzzy => {
+                if collect_indices {
+                    match matcher.fuzzy_indices(item, &self.text) {
+                        Some((score, indices)) => (true, Some(score), indices),
+                        None => (false, None, Vec::new()),
+                    }
+                } else {
+                    match matcher.fuzzy_match(item, &self.text) {
+                        Some(score) => (true, Some(score), Vec::new()),
+                        None => (false, None, Vec::new()),
+                    }
+                }
+            }
+            _ => {
+                let haystack: Cow<str> = if self.ignore_case {
+                    Cow::Owned(item.to_ascii_lowercase())
+                } else {
+                    Cow::Borrowed(item)
+                };
+                let needle = self.text.as_str();
+                let matched = match self.kind {
+                    AtomKind::Substring => haystack.contains(needle),
+                    AtomKind::Prefix => haystack.starts_with(needle),
+                    AtomKind::Postfix => haystack.ends_with(needle),
+                    AtomKind::Exact => haystack.as_ref() == needle,
+                    AtomKind::Fuzzy => unreachable!("fuzzy handled above"),
+                };
+                (matched, None, Vec::new())
+            }
+        };
+
+        if self.inverse {
+            // Inverse atoms invert the boolean and contribute no score.
+            (!matched, None, Vec::new())
+        } else {
+            (matched, score, indices)
+        }
+    }
+}
+
+/// A scored item produced by the non-interactive matcher.
+struct ScoredMatch {
+    index: usize,
+    score: i64,
+    /// Matched character positions from the first fuzzy atom; empty unless
+    /// indices were requested.
+    indices: Vec<usize>,
+}
+
+/// Parse a full query string into its constituent atoms.
+fn parse_query(query: &str) -> Vec<QueryAtom> {
+    split_query(query)
+        .iter()
+        .filter_map(|term| QueryAtom::parse(term))
+        .collect()
+}
+
+/// Item count from which on scoring is spread across rayon's thread pool. Below
+/// this, the sequential path avoids the thread-pool setup overhead.
+const PARALLEL_THRESHOLD: usize = 1000;
+
+/// Score a single item against all atoms, returning its match (or `None` when
+/// any atom fails). An item is a hit only when every atom matches; the score
+/// and indices come from the first fuzzy atom.
+fn score_one(
+    atoms: &[QueryAtom],
+    matcher: &SkimMatcherV2,
+    index: usize,
+    item: &str,
+    collect_indices: bool,
+) -> Option<ScoredMatch> {
+    let mut first_score = None;
+    let mut first_indices: Option<Vec<usize>> = None;
+    let matched_all = atoms.iter().all(|atom| {
+        let (matched, score, indices) = atom.evaluate(matcher, item, collect_indices);
+        if let Some(score) = score {
+            if first_score.is_none() {
+                first_score = Some(score);
+                first_indices = Some(indices);
+            }
+        }
+        matched
+    });
+
+    matched_all.then(|| ScoredMatch {
+        index,
+        score: first_score.unwrap_or(0),
+        indices: first_indices.unwrap_or_default(),
+    })
+}
+
+/// Score `items` against `query`, returning the hits sorted by descending
+/// score. When `collect_indices` is set each hit also carries the matched
+/// character positions of its first fuzzy atom.
+///
+/// Large inputs are scored in parallel via rayon; the final order is
+/// deterministic — equal-score items keep ascending input order — so results
+/// don't flap between runs regardless of which path ran.
+fn score_items(
+    query: &str,
+    items: &[String],
+    case: CaseMode,
+    collect_indices: bool,
+) -> Vec<ScoredMatch> {
+    // Create a SkimMatcherV2 (same algorithm used by skim) honouring the
+    // requested case mode.
+    let matcher = case.matcher(query);
+
+    let atoms = parse_query(query);
+
+    if atoms.is_empty() {
+        // An empty query matches everything, preserving input order.
+        return items
+            .iter()
+            .enumerate()
+            .map(|(index, _)| ScoredMatch {
+                index,
+                score: 0,
+                indices: Vec::new(),
+            })
+            .collect();
+    }
+
+    let mut matches: Vec<ScoredMatch> = if items.len() >= PARALLEL_THRESHOLD {
+        items
+            .par_iter()
+            .enumerate()
+            .filter_map(|(index, item)| {
+                score_one(&atoms, &matcher, index, item, collect_indices)
+            })
+            .collect()
+    } else {
+        items
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item)| {
+                score_one(&atoms, &matcher, index, item, collect_indices)
+            })
+            .collect()
+    };
+
+    // Sort by score (descending), breaking ties by ascending input index so
+    // the ordering is stable and deterministic across sequential and parallel
+    // runs.
+    matches.sort_by(|a, b| b.score.cmp(&a.score).then(a.index.cmp(&b.index)));
+    matches
+}
+
 /// Perform non-interactive fuzzy matching using fuzzy-matcher
 ///
 /// Args:
@@ -116,24 +438,11 @@ fn perform_interactive_match<'a>(query: &str, items: &'a [String]) -> PyResult<V
 fn perform_non_interactive_match<'a>(
     query: &str,
     items: &'a [String],
+    case: CaseMode,
 ) -> PyResult<Vec<&'a String>> {
-    // Create a SkimMatcherV2 (same algorithm used by skim)
-    let matcher = SkimMatcherV2::default();
-
-    let mut scored_indices: Vec<(i64, usize)> = Vec::with_capacity(items.len());
-
-    for (index, item) in items.iter().enumerate() {
-        if let Some(score) = matcher.fuzzy_match(item, query) {
-            scored_indices.push((score, index));
-        }
-    }
-
-    // Sort by score (descending)
-    scored_indices.sort_by(|a, b| b.0.cmp(&a.0));
-
-    Ok(scored_indices
+    Ok(score_items(query, items, case, false)
         .into_iter()
-        .filter_map(|(_, index)| items.get(index))
+        .filter_map(|m| items.get(m.index))
         .collect())
 }
 
@@ -144,9 +453,18 @@ fn perform_non_interactive_match<'a>(
 ///     items: An iterable of strings to search
 ///     interactive: Whether to run in interactive mode (default: False).
 ///                  When True, shows a UI for selection. When False, returns matches non-interactively.
+///     with_scores: When True, return ``(item, score, indices)`` tuples instead
+///                  of bare strings (non-interactive only).
+///     with_indices: Alias enabling the same tuple output; either flag turns it on.
+///     case: Case-matching mode, one of ``"smart"`` (default — case-insensitive
+///           unless the query contains an uppercase letter), ``"ignore"``, or
+///           ``"respect"``. The mode applies to fuzzy terms; ``'``, ``^`` and
+///           ``$`` terms are always smart-cased, each by its own letters.
 ///
 /// Returns:
-///     A list of matched items
+///     A list of matched items, or a list of ``(item, score, indices)`` tuples
+///     when ``with_scores``/``with_indices`` is set, where ``indices`` are the
+///     matched character positions.
 ///
 /// Raises:
 ///     TypeError: If None is found in the items
@@ -158,7 +476,11 @@ fn fuzzy_match(
     query: &str,
     items: &PyAny,
     interactive: Option<bool>,
+    with_scores: Option<bool>,
+    with_indices: Option<bool>,
+    case: Option<&str>,
 ) -> PyResult<PyObject> {
+    let case_mode = CaseMode::parse(case)?;
     let iter = items.iter()?;
 
     // Get the length of the iterator if it's a sequence
@@ -201,7 +523,22 @@ fn fuzzy_match(
     }
 
     let is_interactive = interactive.unwrap_or(false);
-    let matched_items = perform_fuzzy_match(query, &item_strs, is_interactive)?;
+    let want_details = with_scores.unwrap_or(false) || with_indices.unwrap_or(false);
+
+    // Scores and indices are only meaningful for the non-interactive matcher.
+    if want_details && !is_interactive {
+        let matches = score_items(query, &item_strs, case_mode, true);
+        let tuples = matches.into_iter().filter_map(|m| {
+            item_strs.get(m.index).map(|item| {
+                let indices = PyList::new(py, m.indices.iter().map(|&i| i));
+                PyTuple::new(py, &[item.into_py(py), m.score.into_py(py), indices.into()])
+            })
+        });
+        let py_results = PyList::new(py, tuples);
+        return Ok(py_results.into());
+    }
+
+    let matched_items = perform_fuzzy_match(query, &item_strs, is_interactive, case_mode)?;
 
     let py_results = PyList::new(py, matched_items.iter().map(|&s| s.clone()));
 
@@ -227,7 +564,7 @@ pub fn bench_perform_fuzzy_match<'a>(
     items: &'a [String],
     interactive: bool,
 ) -> PyResult<Vec<&'a String>> {
-    perform_fuzzy_match(query, items, interactive)
+    perform_fuzzy_match(query, items, interactive, CaseMode::Smart)
 }
 
 #[doc(hidden)]
@@ -235,5 +572,5 @@ pub fn bench_perform_non_interactive_match<'a>(
     query: &str,
     items: &'a [String],
 ) -> PyResult<Vec<&'a String>> {
-    perform_non_interactive_match(query, items)
+    perform_non_interactive_match(query, items, CaseMode::Smart)
 }